@@ -1,9 +1,9 @@
-use num::traits::Zero;
+use num::traits::{Zero, One, Float};
 use std::iter::Sum;
-use std::ops::{Add, Index, Sub, Mul};
+use std::ops::{Add, Index, IndexMut, Sub, Mul, Div};
 use core::array::from_fn;
 
-use crate::vector::{TVector, Vec2};
+use crate::vector::TVector;
 
 /// M * N matrix with elements of type T. Stored column-major
 #[derive(Clone,Copy,PartialEq,Debug)]
@@ -58,6 +58,18 @@ impl<T: Copy, const M: usize, const N: usize> TMatrix<T, M, N> {
             }))
         }
     }
+
+    pub fn from_fn<F: Fn(usize, usize) -> T>(f: F) -> Self {
+        Self {
+            data: from_fn(|i| from_fn(|j| f(i, j)))
+        }
+    }
+
+    pub fn transpose(&self) -> TMatrix<T, N, M> {
+        TMatrix {
+            data: from_fn(|i| from_fn(|j| self.data[j][i]))
+        }
+    }
 }
 
 fn element_wise<T: Copy,
@@ -89,6 +101,49 @@ impl<T: Sub<Output = T> + Copy, const M: usize, const N: usize> Sub for TMatrix<
     }
 }
 
+/// Scalar multiplication/division. Implemented for the concrete `f32`/`f64`
+/// element types rather than a generic `T`, since a generic `Mul<T> for
+/// TMatrix<T, M, N>` would overlap with the matrix/vector `Mul` impls below.
+impl<const M: usize, const N: usize> Mul<f32> for TMatrix<f32, M, N> {
+    type Output = Self;
+
+    fn mul(self, scalar: f32) -> Self {
+        Self {
+            data: from_fn(|i| from_fn(|j| self.data[i][j] * scalar))
+        }
+    }
+}
+
+impl<const M: usize, const N: usize> Div<f32> for TMatrix<f32, M, N> {
+    type Output = Self;
+
+    fn div(self, scalar: f32) -> Self {
+        Self {
+            data: from_fn(|i| from_fn(|j| self.data[i][j] / scalar))
+        }
+    }
+}
+
+impl<const M: usize, const N: usize> Mul<f64> for TMatrix<f64, M, N> {
+    type Output = Self;
+
+    fn mul(self, scalar: f64) -> Self {
+        Self {
+            data: from_fn(|i| from_fn(|j| self.data[i][j] * scalar))
+        }
+    }
+}
+
+impl<const M: usize, const N: usize> Div<f64> for TMatrix<f64, M, N> {
+    type Output = Self;
+
+    fn div(self, scalar: f64) -> Self {
+        Self {
+            data: from_fn(|i| from_fn(|j| self.data[i][j] / scalar))
+        }
+    }
+}
+
 impl<T, const M: usize, const N: usize> Index<(usize, usize)> for TMatrix<T, M, N> {
     type Output = T;
 
@@ -97,6 +152,70 @@ impl<T, const M: usize, const N: usize> Index<(usize, usize)> for TMatrix<T, M,
     }
 }
 
+impl<T, const M: usize, const N: usize> IndexMut<(usize, usize)> for TMatrix<T, M, N> {
+    fn index_mut(&mut self, (ind0, ind1): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[ind0][ind1]
+    }
+}
+
+/// Iterator over elements in logical row-major order, despite the
+/// column-major storage
+pub struct RowMajorIter<'a, T, const M: usize, const N: usize> {
+    matrix: &'a TMatrix<T, M, N>,
+    index: usize,
+}
+
+impl<'a, T, const M: usize, const N: usize> Iterator for RowMajorIter<'a, T, M, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= M * N {
+            return None;
+        }
+        let (r, c) = (self.index / N, self.index % N);
+        self.index += 1;
+        Some(&self.matrix.data[c][r])
+    }
+}
+
+/// Mutable counterpart to `RowMajorIter`
+pub struct RowMajorIterMut<'a, T, const M: usize, const N: usize> {
+    matrix: &'a mut TMatrix<T, M, N>,
+    index: usize,
+}
+
+impl<'a, T, const M: usize, const N: usize> Iterator for RowMajorIterMut<'a, T, M, N> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= M * N {
+            return None;
+        }
+        let (r, c) = (self.index / N, self.index % N);
+        self.index += 1;
+        let ptr: *mut T = &mut self.matrix.data[c][r];
+        // SAFETY: each call advances `index`, so every returned reference
+        // points at a distinct element and none of them alias.
+        Some(unsafe { &mut *ptr })
+    }
+}
+
+impl<T, const M: usize, const N: usize> TMatrix<T, M, N> {
+    pub fn iter(&self) -> RowMajorIter<'_, T, M, N> {
+        RowMajorIter { matrix: self, index: 0 }
+    }
+
+    pub fn iter_mut(&mut self) -> RowMajorIterMut<'_, T, M, N> {
+        RowMajorIterMut { matrix: self, index: 0 }
+    }
+}
+
+impl<T: Copy, const M: usize, const N: usize> TMatrix<T, M, N> {
+    pub fn iter_rows(&self) -> impl Iterator<Item = [T ; N]> + '_ {
+        (0..M).map(move |r| from_fn(|c| self.data[c][r]))
+    }
+}
+
 
 /// Matrix-vector multiplication
 impl<T: Clone + Copy + Sum + Add<Output = T> + Mul<Output = T> + Zero, const M: usize, const N: usize> Mul<TVector<T, N>> for TMatrix<T, M, N> {
@@ -131,14 +250,173 @@ impl<T: Clone + Copy + Sum + Add<Output = T> + Mul<Output = T> + Zero,
 
 
 
+/// Identity matrix
+impl<T: Zero + One + Copy, const N: usize> TMatrix<T, N, N> {
+    pub fn identity() -> Self {
+        Self {
+            data: from_fn(|c| from_fn(|r| if c == r { T::one() } else { T::zero() }))
+        }
+    }
+}
+
+/// Integer powers of a square matrix, via exponentiation by squaring
+impl<T: Clone + Copy + Sum + Add<Output = T> + Mul<Output = T> + Zero + One, const N: usize> TMatrix<T, N, N> {
+    pub fn pow(self, exp: u64) -> Self {
+        let mut result = Self::identity();
+        let mut base = self;
+        let mut e = exp;
+
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+
+        result
+    }
+
+    pub fn pow_mut(&mut self, exp: u64) {
+        *self = self.pow(exp);
+    }
+}
+
+impl<T: Float, const N: usize> TMatrix<T, N, N> {
+    /// Gauss-Jordan elimination with partial pivoting, run once and shared
+    /// by `determinant` and `inverse`. Returns the determinant and the
+    /// (possibly meaningless, if singular) augmented inverse buffer.
+    fn gauss_jordan(&self) -> (T, [[T ; N] ; N]) {
+        let mut a = self.data;
+        let mut inv = Self::identity().data;
+        let mut det = T::one();
+
+        for k in 0..N {
+            let mut pivot_row = k;
+            let mut pivot_val = a[k][k].abs();
+            for (r, &val) in a[k].iter().enumerate().skip(k + 1) {
+                let val = val.abs();
+                if val > pivot_val {
+                    pivot_val = val;
+                    pivot_row = r;
+                }
+            }
+
+            if pivot_val < T::epsilon() {
+                return (T::zero(), inv);
+            }
+
+            if pivot_row != k {
+                for c in 0..N {
+                    a[c].swap(k, pivot_row);
+                    inv[c].swap(k, pivot_row);
+                }
+                det = -det;
+            }
+
+            let pivot = a[k][k];
+            det = det * pivot;
+
+            for c in 0..N {
+                a[c][k] = a[c][k] / pivot;
+                inv[c][k] = inv[c][k] / pivot;
+            }
+
+            for r in 0..N {
+                if r == k {
+                    continue;
+                }
+                let factor = a[k][r];
+                for c in 0..N {
+                    a[c][r] = a[c][r] - factor * a[c][k];
+                    inv[c][r] = inv[c][r] - factor * inv[c][k];
+                }
+            }
+        }
+
+        (det, inv)
+    }
+
+    pub fn determinant(&self) -> T {
+        self.gauss_jordan().0
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let (det, inv) = self.gauss_jordan();
+        if det.abs() < T::epsilon() {
+            None
+        } else {
+            Some(Self { data: inv })
+        }
+    }
+}
+
+/// Affine transform constructors for 4x4 (homogeneous) matrices
+impl<T: Float> TMatrix<T, 4, 4> {
+    pub fn translation(x: T, y: T, z: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        Self::new(one, zero, zero, x,
+                  zero, one, zero, y,
+                  zero, zero, one, z,
+                  zero, zero, zero, one)
+    }
+
+    pub fn scaling(x: T, y: T, z: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        Self::new(x, zero, zero, zero,
+                  zero, y, zero, zero,
+                  zero, zero, z, zero,
+                  zero, zero, zero, one)
+    }
+
+    pub fn rotation_x(r: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let (s, c) = (r.sin(), r.cos());
+        Self::new(one, zero, zero, zero,
+                  zero, c, -s, zero,
+                  zero, s, c, zero,
+                  zero, zero, zero, one)
+    }
+
+    pub fn rotation_y(r: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let (s, c) = (r.sin(), r.cos());
+        Self::new(c, zero, s, zero,
+                  zero, one, zero, zero,
+                  -s, zero, c, zero,
+                  zero, zero, zero, one)
+    }
+
+    pub fn rotation_z(r: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let (s, c) = (r.sin(), r.cos());
+        Self::new(c, -s, zero, zero,
+                  s, c, zero, zero,
+                  zero, zero, one, zero,
+                  zero, zero, zero, one)
+    }
+
+    pub fn shearing(x_by_y: T, x_by_z: T, y_by_x: T, y_by_z: T, z_by_x: T, z_by_y: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        Self::new(one, x_by_y, x_by_z, zero,
+                  y_by_x, one, y_by_z, zero,
+                  z_by_x, z_by_y, one, zero,
+                  zero, zero, zero, one)
+    }
+}
+
 /// Shorthands
 pub type Matrix<const M: usize, const N: usize> = TMatrix<f32, M, N>;
+pub type DMatrix<const M: usize, const N: usize> = TMatrix<f64, M, N>;
 
 pub type Mat2 = Matrix<2, 2>;
+pub type Mat4 = Matrix<4, 4>;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::vector::{Vec2, Vec4};
+
     #[test]
     fn can_construct_mat2() {
         let m0 = Mat2::new(1.0, 2.0,
@@ -218,4 +496,320 @@ mod tests {
         assert_eq!(m2[(1, 0)], 8.0 + 20.0 + 36.0);
         assert_eq!(m2[(1, 1)], 32.0 + 50.0 + 72.0);
     }
+
+    #[test]
+    fn can_construct_identity() {
+        let id = Mat2::identity();
+
+        assert_eq!(id[(0, 0)], 1.0);
+        assert_eq!(id[(0, 1)], 0.0);
+        assert_eq!(id[(1, 0)], 0.0);
+        assert_eq!(id[(1, 1)], 1.0);
+    }
+
+    #[test]
+    fn can_compute_determinant() {
+        let m0 = Mat2::new(1.0, 2.0,
+                           3.0, 4.0);
+
+        assert!((m0.determinant() - (1.0 * 4.0 - 2.0 * 3.0)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn singular_matrix_has_zero_determinant_and_no_inverse() {
+        let m0 = Mat2::new(1.0, 2.0,
+                           2.0, 4.0);
+
+        assert_eq!(m0.determinant(), 0.0);
+        assert_eq!(m0.inverse(), None);
+    }
+
+    #[test]
+    fn can_invert_mat2() {
+        let m0 = Mat2::new(4.0, 7.0,
+                           2.0, 6.0);
+
+        let inv = m0.inverse().unwrap();
+
+        let identity = m0 * inv;
+        assert!((identity[(0, 0)] - 1.0).abs() < 1e-5);
+        assert!((identity[(0, 1)] - 0.0).abs() < 1e-5);
+        assert!((identity[(1, 0)] - 0.0).abs() < 1e-5);
+        assert!((identity[(1, 1)] - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn can_invert_mat3() {
+        let m0 = Matrix::<3, 3>::from_array(&[2.0, 0.0, 0.0,
+                                              0.0, 2.0, 0.0,
+                                              0.0, 0.0, 2.0]);
+
+        let inv = m0.inverse().unwrap();
+
+        (0..3).for_each(|i| (0..3).for_each(|j| {
+            let expected = if i == j { 0.5 } else { 0.0 };
+            assert!((inv[(i, j)] - expected).abs() < 1e-5);
+        }));
+    }
+
+    #[test]
+    fn can_invert_mat2_after_pivot_swap() {
+        let m0 = Mat2::new(0.0, 1.0,
+                           2.0, 1.0);
+
+        assert!((m0.determinant() - (-2.0)).abs() < 1e-5);
+
+        let inv = m0.inverse().unwrap();
+        let identity = m0 * inv;
+        assert!((identity[(0, 0)] - 1.0).abs() < 1e-5);
+        assert!((identity[(0, 1)] - 0.0).abs() < 1e-5);
+        assert!((identity[(1, 0)] - 0.0).abs() < 1e-5);
+        assert!((identity[(1, 1)] - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn can_construct_translation() {
+        let t = Mat4::translation(1.0, 2.0, 3.0);
+
+        assert_eq!(t[(3, 0)], 1.0);
+        assert_eq!(t[(3, 1)], 2.0);
+        assert_eq!(t[(3, 2)], 3.0);
+        assert_eq!(t[(3, 3)], 1.0);
+    }
+
+    #[test]
+    fn can_construct_scaling() {
+        let s = Mat4::scaling(2.0, 3.0, 4.0);
+
+        assert_eq!(s[(0, 0)], 2.0);
+        assert_eq!(s[(1, 1)], 3.0);
+        assert_eq!(s[(2, 2)], 4.0);
+        assert_eq!(s[(3, 3)], 1.0);
+    }
+
+    #[test]
+    fn can_compose_transforms() {
+        let m = Mat4::translation(1.0, 0.0, 0.0) * Mat4::scaling(2.0, 2.0, 2.0);
+
+        assert_eq!(m[(3, 0)], 1.0);
+        assert_eq!(m[(0, 0)], 2.0);
+        assert_eq!(m[(1, 1)], 2.0);
+        assert_eq!(m[(2, 2)], 2.0);
+    }
+
+    #[test]
+    fn can_construct_rotation_x() {
+        let r = Mat4::rotation_x(std::f32::consts::FRAC_PI_2);
+        let v = Vec4::new(0.0, 1.0, 0.0, 1.0);
+
+        let rotated = r * v;
+
+        assert!((rotated[0] - 0.0).abs() < 1e-5);
+        assert!((rotated[1] - 0.0).abs() < 1e-5);
+        assert!((rotated[2] - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn can_construct_rotation_y() {
+        let r = Mat4::rotation_y(std::f32::consts::FRAC_PI_2);
+        let v = Vec4::new(0.0, 0.0, 1.0, 1.0);
+
+        let rotated = r * v;
+
+        assert!((rotated[0] - 1.0).abs() < 1e-5);
+        assert!((rotated[1] - 0.0).abs() < 1e-5);
+        assert!((rotated[2] - 0.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn can_construct_rotation_z() {
+        let r = Mat4::rotation_z(std::f32::consts::FRAC_PI_2);
+        let v = Vec4::new(1.0, 0.0, 0.0, 1.0);
+
+        let rotated = r * v;
+
+        assert!((rotated[0] - 0.0).abs() < 1e-5);
+        assert!((rotated[1] - 1.0).abs() < 1e-5);
+        assert!((rotated[2] - 0.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn can_construct_shearing() {
+        let sh = Mat4::shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        let v = Vec4::new(0.0, 1.0, 0.0, 1.0);
+
+        let sheared = sh * v;
+
+        assert_eq!(sheared[0], 1.0);
+        assert_eq!(sheared[1], 1.0);
+        assert_eq!(sheared[2], 0.0);
+    }
+
+    #[test]
+    fn can_mutate_element_via_index_mut() {
+        let mut m0 = Mat2::new(1.0, 2.0,
+                               3.0, 4.0);
+
+        m0[(0, 1)] = 10.0;
+
+        assert_eq!(m0[(0, 1)], 10.0);
+    }
+
+    #[test]
+    fn can_construct_via_from_fn() {
+        let m0 = Matrix::<2, 2>::from_fn(|i, j| (i * 2 + j) as f32);
+
+        assert_eq!(m0[(0, 0)], 0.0);
+        assert_eq!(m0[(0, 1)], 1.0);
+        assert_eq!(m0[(1, 0)], 2.0);
+        assert_eq!(m0[(1, 1)], 3.0);
+    }
+
+    #[test]
+    fn can_iterate_in_row_major_order() {
+        let m0 = Mat2::new(1.0, 2.0,
+                           3.0, 4.0);
+
+        let elems: Vec<f32> = m0.iter().cloned().collect();
+
+        assert_eq!(elems, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn can_mutate_through_iter_mut() {
+        let mut m0 = Mat2::new(1.0, 2.0,
+                               3.0, 4.0);
+
+        m0.iter_mut().for_each(|e| *e *= 2.0);
+
+        assert_eq!(m0[(0, 0)], 2.0);
+        assert_eq!(m0[(0, 1)], 6.0);
+        assert_eq!(m0[(1, 0)], 4.0);
+        assert_eq!(m0[(1, 1)], 8.0);
+    }
+
+    #[test]
+    fn can_iterate_over_rows() {
+        let m0 = Mat2::new(1.0, 2.0,
+                           3.0, 4.0);
+
+        let rows: Vec<[f32 ; 2]> = m0.iter_rows().collect();
+
+        assert_eq!(rows, vec![[1.0, 2.0], [3.0, 4.0]]);
+    }
+
+    #[test]
+    fn pow_zero_is_identity() {
+        let m0 = Mat2::new(1.0, 2.0,
+                           3.0, 4.0);
+
+        assert_eq!(m0.pow(0), Mat2::identity());
+    }
+
+    #[test]
+    fn pow_one_is_self() {
+        let m0 = Mat2::new(1.0, 2.0,
+                           3.0, 4.0);
+
+        assert_eq!(m0.pow(1), m0);
+    }
+
+    #[test]
+    fn pow_matches_repeated_multiplication() {
+        let m0 = Mat2::new(1.0, 2.0,
+                           3.0, 4.0);
+
+        assert_eq!(m0.pow(4), m0 * m0 * m0 * m0);
+    }
+
+    #[test]
+    fn pow_mut_updates_in_place() {
+        let mut m0 = Mat2::new(1.0, 2.0,
+                               3.0, 4.0);
+        let expected = m0 * m0 * m0;
+
+        m0.pow_mut(3);
+
+        assert_eq!(m0, expected);
+    }
+
+    #[test]
+    fn can_transpose_square_matrix() {
+        let m0 = Mat2::new(1.0, 2.0,
+                           3.0, 4.0);
+
+        let t = m0.transpose();
+
+        assert_eq!(t[(0, 0)], 1.0);
+        assert_eq!(t[(0, 1)], 2.0);
+        assert_eq!(t[(1, 0)], 3.0);
+        assert_eq!(t[(1, 1)], 4.0);
+    }
+
+    #[test]
+    fn can_transpose_non_square_matrix() {
+        let m0 = Matrix::<2, 3>::from_array(&[1.0, 2.0, 3.0,
+                                              4.0, 5.0, 6.0]);
+
+        let t = m0.transpose();
+
+        assert_eq!(t[(0, 0)], 1.0);
+        assert_eq!(t[(0, 1)], 2.0);
+        assert_eq!(t[(0, 2)], 3.0);
+        assert_eq!(t[(1, 0)], 4.0);
+        assert_eq!(t[(1, 1)], 5.0);
+        assert_eq!(t[(1, 2)], 6.0);
+    }
+
+    #[test]
+    fn can_scale_matrix_by_scalar() {
+        let m0 = Mat2::new(1.0, 2.0,
+                           3.0, 4.0);
+
+        let scaled = m0 * 2.0;
+
+        assert_eq!(scaled[(0, 0)], 2.0);
+        assert_eq!(scaled[(0, 1)], 6.0);
+        assert_eq!(scaled[(1, 0)], 4.0);
+        assert_eq!(scaled[(1, 1)], 8.0);
+    }
+
+    #[test]
+    fn can_divide_matrix_by_scalar() {
+        let m0 = Mat2::new(2.0, 4.0,
+                           6.0, 8.0);
+
+        let scaled = m0 / 2.0;
+
+        assert_eq!(scaled[(0, 0)], 1.0);
+        assert_eq!(scaled[(0, 1)], 3.0);
+        assert_eq!(scaled[(1, 0)], 2.0);
+        assert_eq!(scaled[(1, 1)], 4.0);
+    }
+
+    #[test]
+    fn can_scale_f64_matrix_by_scalar() {
+        let m0 = DMatrix::<2, 2>::new(1.0, 2.0,
+                                      3.0, 4.0);
+
+        let scaled = m0 * 2.0;
+
+        assert_eq!(scaled[(0, 0)], 2.0);
+        assert_eq!(scaled[(0, 1)], 6.0);
+        assert_eq!(scaled[(1, 0)], 4.0);
+        assert_eq!(scaled[(1, 1)], 8.0);
+    }
+
+    #[test]
+    fn can_divide_f64_matrix_by_scalar() {
+        let m0 = DMatrix::<2, 2>::new(2.0, 4.0,
+                                      6.0, 8.0);
+
+        let scaled = m0 / 2.0;
+
+        assert_eq!(scaled[(0, 0)], 1.0);
+        assert_eq!(scaled[(0, 1)], 3.0);
+        assert_eq!(scaled[(1, 0)], 2.0);
+        assert_eq!(scaled[(1, 1)], 4.0);
+    }
 }
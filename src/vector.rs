@@ -1,6 +1,7 @@
-use std::ops::{Add, Index, Sub, Mul, Div};
+use std::ops::{Add, Index, IndexMut, Sub, Mul, Div};
+use std::iter::Sum;
 use core::array::from_fn;
-use num::traits::Zero;
+use num::traits::{Zero, Float};
 
 #[derive(Clone,Copy,PartialEq,Debug)]
 pub struct TVector<T, const N: usize> {
@@ -90,6 +91,49 @@ impl<T: Div<Output = T> + Copy, const N: usize> Div for TVector<T, N> {
     }
 }
 
+/// Scalar multiplication/division. Implemented for the concrete `f32`/`f64`
+/// element types rather than a generic `T`, since a generic `Mul<T> for
+/// TVector<T, N>` would overlap with the element-wise `Mul` impl above.
+impl<const N: usize> Mul<f32> for TVector<f32, N> {
+    type Output = Self;
+
+    fn mul(self, scalar: f32) -> Self {
+        Self {
+            data: from_fn(|i| self.data[i] * scalar)
+        }
+    }
+}
+
+impl<const N: usize> Div<f32> for TVector<f32, N> {
+    type Output = Self;
+
+    fn div(self, scalar: f32) -> Self {
+        Self {
+            data: from_fn(|i| self.data[i] / scalar)
+        }
+    }
+}
+
+impl<const N: usize> Mul<f64> for TVector<f64, N> {
+    type Output = Self;
+
+    fn mul(self, scalar: f64) -> Self {
+        Self {
+            data: from_fn(|i| self.data[i] * scalar)
+        }
+    }
+}
+
+impl<const N: usize> Div<f64> for TVector<f64, N> {
+    type Output = Self;
+
+    fn div(self, scalar: f64) -> Self {
+        Self {
+            data: from_fn(|i| self.data[i] / scalar)
+        }
+    }
+}
+
 /// Index implementation for vectors
 impl<T, const N: usize> Index<usize> for TVector<T, N> {
     type Output = T;
@@ -99,6 +143,66 @@ impl<T, const N: usize> Index<usize> for TVector<T, N> {
     }
 }
 
+/// IndexMut implementation for vectors
+impl<T, const N: usize> IndexMut<usize> for TVector<T, N> {
+    fn index_mut(&mut self, ind: usize) -> &mut T {
+        &mut self.data[ind]
+    }
+}
+
+impl<T: Copy, const N: usize> TVector<T, N> {
+    pub fn from_fn<F: Fn(usize) -> T>(f: F) -> Self {
+        Self {
+            data: from_fn(f)
+        }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+}
+
+/// Geometric operations for vectors
+impl<T: Mul<Output = T> + Sum + Copy, const N: usize> TVector<T, N> {
+    pub fn dot(self, other: Self) -> T {
+        (0..N).map(|i| self.data[i] * other.data[i]).sum()
+    }
+}
+
+impl<T: Float + Sum, const N: usize> TVector<T, N> {
+    pub fn length_squared(self) -> T {
+        self.dot(self)
+    }
+
+    pub fn length(self) -> T {
+        self.length_squared().sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        let len = self.length();
+        Self {
+            data: from_fn(|i| self.data[i] / len)
+        }
+    }
+}
+
+/// 3D cross product
+impl<T: Mul<Output = T> + Sub<Output = T> + Copy> TVector<T, 3> {
+    pub fn cross(self, other: Self) -> Self {
+        Self {
+            data: [
+                self.data[1] * other.data[2] - self.data[2] * other.data[1],
+                self.data[2] * other.data[0] - self.data[0] * other.data[2],
+                self.data[0] * other.data[1] - self.data[1] * other.data[0],
+            ]
+        }
+    }
+}
+
 
 /// Shorthands
 pub type Vector<const N: usize> = TVector<f32, N>;
@@ -197,4 +301,95 @@ mod tests {
         assert_eq!(v2[1], 3.0 / 2.0);
         assert_eq!(v2[2], 4.0 / 1.0);
     }
+
+    #[test]
+    fn can_compute_dot_product() {
+        let v0 = Vec3::new(1.0, 2.0, 3.0);
+        let v1 = Vec3::new(4.0, 5.0, 6.0);
+
+        assert_eq!(v0.dot(v1), 1.0 * 4.0 + 2.0 * 5.0 + 3.0 * 6.0);
+    }
+
+    #[test]
+    fn can_compute_length() {
+        let v0 = Vec3::new(3.0, 4.0, 0.0);
+
+        assert_eq!(v0.length_squared(), 25.0);
+        assert_eq!(v0.length(), 5.0);
+    }
+
+    #[test]
+    fn can_normalize_vector() {
+        let v0 = Vec3::new(3.0, 4.0, 0.0);
+
+        let n = v0.normalize();
+
+        assert_eq!(n[0], 3.0 / 5.0);
+        assert_eq!(n[1], 4.0 / 5.0);
+        assert_eq!(n[2], 0.0);
+        assert!((n.length() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn can_compute_cross_product() {
+        let v0 = Vec3::new(1.0, 0.0, 0.0);
+        let v1 = Vec3::new(0.0, 1.0, 0.0);
+
+        let v2 = v0.cross(v1);
+
+        assert_eq!(v2[0], 0.0);
+        assert_eq!(v2[1], 0.0);
+        assert_eq!(v2[2], 1.0);
+    }
+
+    #[test]
+    fn can_mutate_element_via_index_mut() {
+        let mut v0 = Vec3::new(1.0, 2.0, 3.0);
+
+        v0[1] = 10.0;
+
+        assert_eq!(v0[1], 10.0);
+    }
+
+    #[test]
+    fn can_construct_via_from_fn() {
+        let v0 = Vec3::from_fn(|i| i as f32 * 2.0);
+
+        assert_eq!(v0[0], 0.0);
+        assert_eq!(v0[1], 2.0);
+        assert_eq!(v0[2], 4.0);
+    }
+
+    #[test]
+    fn can_iterate_and_mutate_through_iter() {
+        let mut v0 = Vec3::new(1.0, 2.0, 3.0);
+
+        assert_eq!(v0.iter().cloned().collect::<Vec<f32>>(), vec![1.0, 2.0, 3.0]);
+
+        v0.iter_mut().for_each(|e| *e *= 2.0);
+
+        assert_eq!(v0.data, [2.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn can_scale_vector_by_scalar() {
+        let v0 = Vec3::new(1.0, 2.0, 3.0);
+
+        let v1 = v0 * 2.0;
+
+        assert_eq!(v1[0], 2.0);
+        assert_eq!(v1[1], 4.0);
+        assert_eq!(v1[2], 6.0);
+    }
+
+    #[test]
+    fn can_divide_vector_by_scalar() {
+        let v0 = Vec3::new(2.0, 4.0, 6.0);
+
+        let v1 = v0 / 2.0;
+
+        assert_eq!(v1[0], 1.0);
+        assert_eq!(v1[1], 2.0);
+        assert_eq!(v1[2], 3.0);
+    }
 }